@@ -1,9 +1,11 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::process::Command;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 
 const CLI_INSTALL_DIR: &str = ".opencode/bin";
 const CLI_BINARY_NAME: &str = "opencode";
+const CLI_VERSION_FILE: &str = ".opencode/.version";
+const CLI_PIN_FILE: &str = ".opencode/.pinned";
 
 #[derive(Debug, Deserialize)]
 pub struct Config {
@@ -51,11 +53,282 @@ pub fn get_config(sidecar_path: &std::path::Path) -> Option<Config> {
     serde_json::from_str(&stdout).ok()
 }
 
+fn get_cli_install_dir() -> Option<std::path::PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| std::path::PathBuf::from(home).join(CLI_INSTALL_DIR))
+}
+
 fn get_cli_install_path() -> Option<std::path::PathBuf> {
-    std::env::var("HOME").ok().map(|home| {
-        std::path::PathBuf::from(home)
-            .join(CLI_INSTALL_DIR)
-            .join(CLI_BINARY_NAME)
+    get_cli_install_dir().map(|dir| dir.join(CLI_BINARY_NAME))
+}
+
+/// Path of the binary for a specific installed version, e.g. `~/.opencode/bin/opencode-0.3.1`.
+fn version_binary_path(version: &semver::Version) -> Option<std::path::PathBuf> {
+    get_cli_install_dir().map(|dir| dir.join(format!("{}-{}", CLI_BINARY_NAME, version)))
+}
+
+fn get_version_file_path() -> Option<std::path::PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| std::path::PathBuf::from(home).join(CLI_VERSION_FILE))
+}
+
+/// Read the active version recorded in `~/.opencode/.version`.
+///
+/// A missing or corrupt file is treated as "no active install" and yields `None`.
+fn read_active_version() -> Option<semver::Version> {
+    let path = get_version_file_path()?;
+    let contents = std::fs::read_to_string(path).ok()?;
+    semver::Version::parse(contents.trim()).ok()
+}
+
+fn active_binary_path() -> Option<std::path::PathBuf> {
+    version_binary_path(&read_active_version()?)
+}
+
+/// Atomically rewrite `~/.opencode/.version` to point at `version`.
+fn write_active_version(version: &semver::Version) -> Result<(), String> {
+    let path =
+        get_version_file_path().ok_or_else(|| "Could not determine version file path".to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create opencode dir: {}", e))?;
+    }
+
+    let temp = path.with_file_name(".version.tmp");
+    std::fs::write(&temp, format!("{}\n", version))
+        .map_err(|e| format!("Failed to write version file: {}", e))?;
+    std::fs::rename(&temp, &path)
+        .map_err(|e| format!("Failed to update version file: {}", e))?;
+
+    Ok(())
+}
+
+fn get_pin_file_path() -> Option<std::path::PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| std::path::PathBuf::from(home).join(CLI_PIN_FILE))
+}
+
+/// Whether the active version was explicitly chosen by the user (via
+/// `use_cli_version` or `use_cli_with_dir`) rather than by the installer or `sync_cli`.
+///
+/// `sync_cli` must not silently re-pin the bundled version over a pin like this,
+/// or a deliberate rollback to an older release would be undone on next launch.
+fn is_version_pinned() -> bool {
+    get_pin_file_path()
+        .map(|path| path.exists())
+        .unwrap_or(false)
+}
+
+/// Record or clear the "user pinned this version" marker.
+fn set_version_pinned(pinned: bool) -> Result<(), String> {
+    let path = get_pin_file_path().ok_or_else(|| "Could not determine pin file path".to_string())?;
+    if pinned {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create opencode dir: {}", e))?;
+        }
+        std::fs::write(&path, b"").map_err(|e| format!("Failed to write pin marker: {}", e))?;
+    } else {
+        let _ = std::fs::remove_file(&path);
+    }
+    Ok(())
+}
+
+/// Every version installed side by side under `~/.opencode/bin`, sorted ascending.
+fn list_installed_versions() -> Vec<semver::Version> {
+    let Some(dir) = get_cli_install_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let prefix = format!("{}-", CLI_BINARY_NAME);
+    let mut versions: Vec<semver::Version> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter_map(|name| {
+            name.strip_prefix(&prefix)
+                .and_then(|rest| semver::Version::parse(rest).ok())
+        })
+        .collect();
+    versions.sort();
+    versions
+}
+
+/// Where a discovered opencode binary came from.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CliSource {
+    /// Found on `PATH`.
+    Path,
+    /// Found in a common platform install location.
+    System,
+    /// Found via the Windows registry.
+    Registry,
+    /// Registered by the user from a custom directory.
+    Custom,
+}
+
+/// A usable opencode binary found somewhere on the machine.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoveredCli {
+    pub path: std::path::PathBuf,
+    pub version: semver::Version,
+    pub source: CliSource,
+}
+
+/// Run `<path> --version` and parse the output as semver.
+fn read_binary_version(path: &std::path::Path) -> Option<semver::Version> {
+    let output = std::process::Command::new(path)
+        .arg("--version")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    semver::Version::parse(stdout.trim()).ok()
+}
+
+/// A discovered CLI is usable as long as it shares the app's major version.
+///
+/// Pre-1.0 releases use `minor` as the breaking-change boundary (caret semantics),
+/// so below major `1` this also requires a matching `minor`.
+fn is_version_compatible(cli: &semver::Version, app: &semver::Version) -> bool {
+    if app.major == 0 {
+        cli.major == 0 && cli.minor == app.minor
+    } else {
+        cli.major == app.major
+    }
+}
+
+fn path_candidates() -> Vec<std::path::PathBuf> {
+    let binary = if cfg!(windows) {
+        "opencode.exe"
+    } else {
+        CLI_BINARY_NAME
+    };
+    std::env::var_os("PATH")
+        .map(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join(binary))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn probe_candidate(
+    path: &std::path::Path,
+    source: CliSource,
+    app_version: &semver::Version,
+) -> Option<DiscoveredCli> {
+    if !path.exists() {
+        return None;
+    }
+    let version = read_binary_version(path)?;
+    if !is_version_compatible(&version, app_version) {
+        return None;
+    }
+    Some(DiscoveredCli {
+        path: path.to_path_buf(),
+        version,
+        source,
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn registry_install_path(app: &tauri::AppHandle) -> Option<std::path::PathBuf> {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    let identifier = app.config().identifier.clone();
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let key = hkcu.open_subkey(format!("Software\\{}", identifier)).ok()?;
+    let location: String = key.get_value("InstallLocation").ok()?;
+    Some(std::path::PathBuf::from(location).join("opencode.exe"))
+}
+
+/// Look for a usable, already-installed opencode before falling back to the sidecar.
+///
+/// On every platform this first searches `PATH`; on macOS it then probes the common
+/// Homebrew locations, and on Windows the install location from the registry.
+#[tauri::command]
+pub fn discover_cli(app: tauri::AppHandle) -> Option<DiscoveredCli> {
+    let app_version = app.package_info().version.clone();
+
+    for candidate in path_candidates() {
+        if let Some(found) = probe_candidate(&candidate, CliSource::Path, &app_version) {
+            return Some(found);
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    for dir in ["/opt/homebrew/bin", "/usr/local/bin"] {
+        let candidate = std::path::PathBuf::from(dir).join(CLI_BINARY_NAME);
+        if let Some(found) = probe_candidate(&candidate, CliSource::System, &app_version) {
+            return Some(found);
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    if let Some(candidate) = registry_install_path(&app) {
+        if let Some(found) = probe_candidate(&candidate, CliSource::Registry, &app_version) {
+            return Some(found);
+        }
+    }
+
+    None
+}
+
+/// Register an opencode binary kept in a nonstandard directory without running the
+/// installer. The binary is linked into `~/.opencode/bin` under its version and
+/// made active so the rest of the version manager can resolve it.
+///
+/// This marks the registered version as user-pinned, the same as `use_cli_version`,
+/// so `sync_cli` doesn't silently replace it with the bundled app version.
+#[tauri::command]
+pub fn use_cli_with_dir(dir: std::path::PathBuf) -> Result<DiscoveredCli, String> {
+    let binary = if cfg!(windows) {
+        "opencode.exe"
+    } else {
+        CLI_BINARY_NAME
+    };
+    let path = dir.join(binary);
+    if !path.exists() {
+        return Err(format!("No opencode binary found at {}", path.display()));
+    }
+
+    let version =
+        read_binary_version(&path).ok_or_else(|| "Failed to read opencode version".to_string())?;
+
+    let dest = version_binary_path(&version)
+        .ok_or_else(|| "Could not determine versioned binary path".to_string())?;
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create opencode dir: {}", e))?;
+    }
+    if dest.exists() {
+        let _ = std::fs::remove_file(&dest);
+    }
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&path, &dest)
+        .map_err(|e| format!("Failed to register binary: {}", e))?;
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_file(&path, &dest)
+        .map_err(|e| format!("Failed to register binary: {}", e))?;
+
+    write_active_version(&version)?;
+    set_version_pinned(true)?;
+
+    Ok(DiscoveredCli {
+        path,
+        version,
+        source: CliSource::Custom,
     })
 }
 
@@ -69,23 +342,45 @@ pub fn get_sidecar_path(app: &tauri::AppHandle) -> std::path::PathBuf {
 }
 
 fn is_cli_installed() -> bool {
-    get_cli_install_path()
+    active_binary_path()
         .map(|path| path.exists())
         .unwrap_or(false)
 }
 
 const INSTALL_SCRIPT: &str = include_str!("../../../../install");
 
-#[tauri::command]
-pub fn install_cli(app: tauri::AppHandle) -> Result<String, String> {
-    if cfg!(not(unix)) {
-        return Err("CLI installation is only supported on macOS & Linux".to_string());
-    }
+/// A single line of install output, emitted as a `cli-install-progress` event.
+///
+/// The install script reports no machine-readable progress, so this is log-only:
+/// there is no `percent` field, and the frontend should render it as a scrolling
+/// log rather than a determinate progress bar.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallProgress {
+    pub phase: String,
+    pub message: String,
+}
 
-    let sidecar = get_sidecar_path(&app);
-    if !sidecar.exists() {
-        return Err("Sidecar binary not found".to_string());
-    }
+/// The terminal `cli-install-complete` event: resolved install path or error.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallComplete {
+    pub path: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Run the bundled install script, which writes the sidecar to `~/.opencode/bin/opencode`.
+///
+/// Both streams are piped and read line-by-line on background threads. When
+/// `emit_progress` is set, each line is forwarded to the frontend as a
+/// `cli-install-progress` event so the UI can render a live log during the
+/// multi-second download; a background `sync_cli` pass sets it to `false` so a
+/// routine startup sync doesn't drive UI that never asked for an install.
+fn run_install_script(
+    app: &tauri::AppHandle,
+    sidecar: &std::path::Path,
+    emit_progress: bool,
+) -> Result<(), String> {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
 
     let temp_script = std::env::temp_dir().join("opencode-install.sh");
     std::fs::write(&temp_script, INSTALL_SCRIPT)
@@ -98,51 +393,328 @@ pub fn install_cli(app: tauri::AppHandle) -> Result<String, String> {
             .map_err(|e| format!("Failed to set script permissions: {}", e))?;
     }
 
-    let output = std::process::Command::new(&temp_script)
+    let mut child = std::process::Command::new(&temp_script)
         .arg("--binary")
-        .arg(&sidecar)
-        .output()
+        .arg(sidecar)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
         .map_err(|e| format!("Failed to run install script: {}", e))?;
 
+    let stdout_handle = child.stdout.take().map(|stdout| {
+        let app = app.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if emit_progress {
+                    let _ = app.emit(
+                        "cli-install-progress",
+                        InstallProgress {
+                            phase: "install".to_string(),
+                            message: line,
+                        },
+                    );
+                }
+            }
+        })
+    });
+
+    let stderr_handle = child.stderr.take().map(|stderr| {
+        let app = app.clone();
+        std::thread::spawn(move || {
+            let mut collected = String::new();
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                if emit_progress {
+                    let _ = app.emit(
+                        "cli-install-progress",
+                        InstallProgress {
+                            phase: "error".to_string(),
+                            message: line.clone(),
+                        },
+                    );
+                }
+                collected.push_str(&line);
+                collected.push('\n');
+            }
+            collected
+        })
+    });
+
+    if let Some(handle) = stdout_handle {
+        let _ = handle.join();
+    }
+    let stderr_text = stderr_handle
+        .map(|handle| handle.join().unwrap_or_default())
+        .unwrap_or_default();
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for install script: {}", e))?;
+
     let _ = std::fs::remove_file(&temp_script);
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Install script failed: {}", stderr));
+    if !status.success() {
+        return Err(format!("Install script failed: {}", stderr_text.trim()));
     }
 
-    let install_path =
+    Ok(())
+}
+
+fn aside_path(dest: &std::path::Path) -> std::path::PathBuf {
+    let name = dest
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(CLI_BINARY_NAME);
+    dest.with_file_name(format!("{}.old", name))
+}
+
+/// Move `src` into `dest` safely, even when `dest` is a running binary.
+///
+/// The source is made executable and moved with an atomic `rename`. If the
+/// destination is busy (`ETXTBSY`) or otherwise can't be replaced, the old binary
+/// is renamed aside (`<name>.old`) and deleted on the next successful sync.
+fn install_binary(src: &std::path::Path, dest: &std::path::Path) -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(src, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to set binary permissions: {}", e))?;
+    }
+
+    if let Err(first) = std::fs::rename(src, dest) {
+        if dest.exists() {
+            let aside = aside_path(dest);
+            let _ = std::fs::remove_file(&aside);
+            std::fs::rename(dest, &aside)
+                .map_err(|e| format!("Failed to move in-use binary aside: {}", e))?;
+        }
+        std::fs::rename(src, dest)
+            .map_err(|e| format!("Failed to install binary (first attempt: {}): {}", first, e))?;
+    }
+
+    Ok(())
+}
+
+/// Delete any `<name>.old` binaries left behind by a previous in-use replacement.
+fn cleanup_stale_binaries() {
+    let Some(dir) = get_cli_install_dir() else {
+        return;
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        if entry
+            .file_name()
+            .to_str()
+            .map(|name| name.ends_with(".old"))
+            .unwrap_or(false)
+        {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Install the bundled sidecar as `version`, placing it in its own versioned slot
+/// and marking it active. Existing versions are left in place so users can roll back.
+///
+/// Emits no `cli-install-complete` event; the only events it can cause are the
+/// `cli-install-progress` lines from `run_install_script`, when `emit_progress` is set. Used directly by `sync_cli`'s background pass and
+/// wrapped by the `install_cli_version` command for user-initiated installs.
+fn install_version(
+    app: &tauri::AppHandle,
+    version: semver::Version,
+    emit_progress: bool,
+) -> Result<String, String> {
+    if cfg!(not(unix)) {
+        return Err("CLI installation is only supported on macOS & Linux".to_string());
+    }
+
+    let sidecar = get_sidecar_path(app);
+    if !sidecar.exists() {
+        return Err("Sidecar binary not found".to_string());
+    }
+
+    run_install_script(app, &sidecar, emit_progress)?;
+
+    // The install script can only ever write the bundled sidecar, so what actually
+    // landed is whatever version *that* binary reports, not necessarily `version`.
+    let installed =
         get_cli_install_path().ok_or_else(|| "Could not determine install path".to_string())?;
+    let actual = read_binary_version(&installed)
+        .ok_or_else(|| "Failed to read installed CLI version".to_string())?;
+    if actual != version {
+        return Err(format!(
+            "Installed CLI reports version {} but {} was requested; the installer can only provide the bundled app version",
+            actual, version
+        ));
+    }
+
+    let dest = version_binary_path(&actual)
+        .ok_or_else(|| "Could not determine versioned binary path".to_string())?;
+    install_binary(&installed, &dest)?;
+
+    write_active_version(&actual)?;
+    // An explicit install supersedes any earlier user-pinned rollback.
+    set_version_pinned(false)?;
 
-    Ok(install_path.to_string_lossy().to_string())
+    Ok(dest.to_string_lossy().to_string())
 }
 
-pub fn sync_cli(app: tauri::AppHandle) -> Result<(), String> {
-    if cfg!(debug_assertions) {
-        println!("Skipping CLI sync for debug build");
-        return Ok(());
+/// Install the bundled sidecar as `version` on behalf of the user, streaming
+/// `cli-install-progress` events while the script runs and then a terminal
+/// `cli-install-complete` event carrying the resolved install path or the error.
+///
+/// `sync_cli`'s own background install goes through `install_version` directly so a
+/// routine startup sync doesn't fire these events at a frontend that never asked
+/// for an install.
+#[tauri::command]
+pub fn install_cli_version(app: tauri::AppHandle, version: semver::Version) -> Result<String, String> {
+    let result = install_version(&app, version, true);
+
+    let complete = match &result {
+        Ok(path) => InstallComplete {
+            path: Some(path.clone()),
+            error: None,
+        },
+        Err(error) => InstallComplete {
+            path: None,
+            error: Some(error.clone()),
+        },
+    };
+    let _ = app.emit("cli-install-complete", complete);
+
+    result
+}
+
+/// Versions currently installed side by side.
+#[tauri::command]
+pub fn list_installed_cli() -> Vec<semver::Version> {
+    list_installed_versions()
+}
+
+/// Switch the active version pointer to an already-installed version.
+///
+/// This marks the version as user-pinned, so `sync_cli` will not silently
+/// re-pin a newer bundled release over a deliberate rollback.
+#[tauri::command]
+pub fn use_cli_version(version: semver::Version) -> Result<(), String> {
+    let path = version_binary_path(&version)
+        .ok_or_else(|| "Could not determine versioned binary path".to_string())?;
+    if !path.exists() {
+        return Err(format!("opencode {} is not installed", version));
     }
 
-    if !is_cli_installed() {
-        println!("No CLI installation found, skipping sync");
-        return Ok(());
+    write_active_version(&version)?;
+    set_version_pinned(true)
+}
+
+#[tauri::command]
+pub fn install_cli(app: tauri::AppHandle) -> Result<String, String> {
+    let version = app.package_info().version.clone();
+    install_cli_version(app, version)
+}
+
+/// How the installed CLI compares to the running app.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CliStatus {
+    UpToDate,
+    Outdated,
+    Missing,
+}
+
+/// The `[server]` section of the parsed opencode config.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticsServer {
+    pub hostname: Option<String>,
+    pub port: Option<u32>,
+}
+
+/// A one-shot snapshot of the opencode integration's health, inspired by `tauri info`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostics {
+    pub shell: String,
+    pub sidecar_path: String,
+    pub sidecar_exists: bool,
+    pub cli_install_path: Option<String>,
+    pub cli_installed: bool,
+    pub cli_version: Option<String>,
+    pub app_version: String,
+    pub status: CliStatus,
+    pub server: Option<DiagnosticsServer>,
+    pub errors: Vec<String>,
+}
+
+/// Collect everything needed to debug a broken setup into one structured payload.
+///
+/// Each check records its own error string in `errors` rather than aborting, so the
+/// frontend can render a health panel and users can paste the result into bug reports.
+#[tauri::command]
+pub fn diagnose(app: tauri::AppHandle) -> Diagnostics {
+    let mut errors = Vec::new();
+
+    let shell = get_user_shell();
+
+    let sidecar = get_sidecar_path(&app);
+    let sidecar_exists = sidecar.exists();
+    if !sidecar_exists {
+        errors.push("Sidecar binary not found".to_string());
     }
 
-    let cli_path =
-        get_cli_install_path().ok_or_else(|| "Could not determine CLI install path".to_string())?;
+    let install_path = active_binary_path();
+    let cli_installed = is_cli_installed();
+    if !cli_installed {
+        errors.push("No active opencode installation found".to_string());
+    }
 
-    let output = std::process::Command::new(&cli_path)
-        .arg("--version")
-        .output()
-        .map_err(|e| format!("Failed to get CLI version: {}", e))?;
+    let app_version = app.package_info().version.clone();
 
-    if !output.status.success() {
-        return Err("Failed to get CLI version".to_string());
+    let cli_version = install_path.as_ref().and_then(|path| read_binary_version(path));
+    if cli_installed && cli_version.is_none() {
+        errors.push("Failed to read installed CLI version".to_string());
+    }
+
+    let status = match &cli_version {
+        None => CliStatus::Missing,
+        Some(version) if *version >= app_version => CliStatus::UpToDate,
+        Some(_) => CliStatus::Outdated,
+    };
+
+    let server = match get_config(&sidecar) {
+        Some(config) => config.server.map(|server| DiagnosticsServer {
+            hostname: server.hostname,
+            port: server.port,
+        }),
+        None => {
+            errors.push("Failed to read opencode config via `debug config`".to_string());
+            None
+        }
+    };
+
+    Diagnostics {
+        shell,
+        sidecar_path: sidecar.to_string_lossy().to_string(),
+        sidecar_exists,
+        cli_install_path: install_path.map(|path| path.to_string_lossy().to_string()),
+        cli_installed,
+        cli_version: cli_version.map(|version| version.to_string()),
+        app_version: app_version.to_string(),
+        status,
+        server,
+        errors,
     }
+}
 
-    let cli_version_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    let cli_version = semver::Version::parse(&cli_version_str)
-        .map_err(|e| format!("Failed to parse CLI version '{}': {}", cli_version_str, e))?;
+pub fn sync_cli(app: tauri::AppHandle) -> Result<(), String> {
+    if cfg!(debug_assertions) {
+        println!("Skipping CLI sync for debug build");
+        return Ok(());
+    }
+
+    let Some(cli_version) = read_active_version() else {
+        println!("No active CLI installation found, skipping sync");
+        return Ok(());
+    };
 
     let app_version = app.package_info().version.clone();
 
@@ -154,12 +726,31 @@ pub fn sync_cli(app: tauri::AppHandle) -> Result<(), String> {
         return Ok(());
     }
 
+    if is_version_pinned() {
+        println!(
+            "CLI version {} is user-pinned, skipping sync to app version {}",
+            cli_version, app_version
+        );
+        return Ok(());
+    }
+
     println!(
         "CLI version {} is older than app version {}, syncing",
         cli_version, app_version
     );
 
-    install_cli(app)?;
+    // Install the newer bundled version alongside the current one and only then
+    // move the `.version` pointer, leaving the old binary in place for rollback.
+    if version_binary_path(&app_version)
+        .map(|path| path.exists())
+        .unwrap_or(false)
+    {
+        write_active_version(&app_version)?;
+    } else {
+        install_version(&app, app_version, false)?;
+    }
+
+    cleanup_stale_binaries();
 
     println!("Synced installed CLI");
 